@@ -7,15 +7,101 @@ use proc_macro2::TokenStream;
 
 use quote::quote;
 
-use syn::Token;
 use syn::punctuated::Punctuated;
+use syn::Token;
+
+/// The integer type backing a `Codec`'s bit-level representation, chosen
+/// from the enum's `#[repr(...)]` attribute. Widening beyond `u8` unlocks
+/// `Codec::BITS` values above 8, for alphabets such as codon tables or
+/// quality-annotated bases that don't fit in a byte.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BackingInt {
+    U8,
+    U16,
+    U32,
+}
+
+impl BackingInt {
+    /// Number of bits available in this backing integer, and therefore the
+    /// largest legal `#[bits(N)]` request.
+    pub(crate) fn capacity(self) -> u8 {
+        match self {
+            BackingInt::U8 => 8,
+            BackingInt::U16 => 16,
+            BackingInt::U32 => 32,
+        }
+    }
+
+    /// The `syn::Ident` for this type, for use in derived code (`to_bits`,
+    /// `from_bits`, and friends).
+    pub(crate) fn ty(self) -> syn::Ident {
+        let name = match self {
+            BackingInt::U8 => "u8",
+            BackingInt::U16 => "u16",
+            BackingInt::U32 => "u32",
+        };
+        syn::Ident::new(name, proc_macro2::Span::call_site())
+    }
+
+    /// Build a `value` literal suffixed with this backing type (e.g. `5u16`)
+    /// so it type-checks as a match arm pattern against a `to_bits`/
+    /// `from_bits` scrutinee of that type.
+    pub(crate) fn suffixed_lit(self, value: u64) -> syn::LitInt {
+        syn::LitInt::new(
+            &format!("{value}{}", self.ty()),
+            proc_macro2::Span::call_site(),
+        )
+    }
+}
+
+/// Inspect the enum's `#[repr(...)]` attribute to choose the integer type
+/// that backs its bit-level representation. Defaults to `u8` when no
+/// `#[repr(...)]` is present, matching the crate's original 8-bit-only
+/// behaviour.
+pub(crate) fn parse_repr(attrs: &[syn::Attribute]) -> Result<BackingInt, syn::Error> {
+    for attr in attrs {
+        if attr.path().is_ident("repr") {
+            let ident: syn::Ident = attr.parse_args()?;
+            return match ident.to_string().as_str() {
+                "u8" => Ok(BackingInt::U8),
+                "u16" => Ok(BackingInt::U16),
+                "u32" => Ok(BackingInt::U32),
+                other => Err(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "Codec derivations do not support #[repr({other})]; use u8, u16, or u32"
+                    ),
+                )),
+            };
+        }
+    }
+    Ok(BackingInt::U8)
+}
 
 /// Allow the user to request more bits than used by their encodings
-pub(crate) fn parse_width(attrs: &Vec<syn::Attribute>, max_variant: u8) -> Result<u8, syn::Error> {
+pub(crate) fn parse_width(
+    attrs: &Vec<syn::Attribute>,
+    max_variant: u32,
+    backing: BackingInt,
+) -> Result<u8, syn::Error> {
     // minimum width is the log2 of the max_variant
     #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::cast_sign_loss)]
-    let min_width: u8 = f32::ceil(f32::log2(f32::from(max_variant + 1))) as u8;
+    let min_width: u8 = f64::ceil(f64::log2(f64::from(max_variant) + 1.0)) as u8;
+
+    // the enum's discriminants alone may already need more bits than the
+    // backing #[repr(...)] type can hold, independent of any #[bits(N)]
+    // request
+    if min_width > backing.capacity() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!(
+                "Encoding all variants needs {min_width} bits, which exceeds the {} bits available in #[repr({})]",
+                backing.capacity(),
+                backing.ty()
+            ),
+        ));
+    }
 
     for attr in attrs {
         if attr.path().is_ident("bits") {
@@ -30,6 +116,17 @@ pub(crate) fn parse_width(attrs: &Vec<syn::Attribute>, max_variant: u8) -> Resul
                                 "Bit width is not large enough encode all variants (min: {min_width})"
                             ),
                         ))
+                    } else if chosen_width > backing.capacity() {
+                        // the backing #[repr(...)] type can't hold this many bits
+                        Err(syn::Error::new_spanned(
+                            attr,
+                            format!(
+                                "Bit width exceeds the {} bits available in #[repr({})] (max: {})",
+                                backing.capacity(),
+                                backing.ty(),
+                                backing.capacity()
+                            ),
+                        ))
                     } else {
                         Ok(chosen_width)
                     }
@@ -76,26 +173,47 @@ pub(crate) struct CodecVariants {
     pub(crate) idents: Vec<syn::Ident>,
     /// the ASCII bytes that symbols are printed to
     pub(crate) to_chars: Vec<TokenStream>,
-    /// ASCII bytes that symbols are read from
+    /// ASCII bytes that symbols are read from, including `#[alt(...)]` bytes
+    /// that would be invalid idents (e.g. `*`) -- these feed `try_from_ascii`.
     pub(crate) from_chars: Vec<TokenStream>,
-    /// Alternative ASCII bytes that symbols are read from that would be invalid idents (e.g. `*`)
+    /// `<backing-typed literal> => Some(Self::ident)` arms for `try_from_bits`,
+    /// one per discriminant.
     pub(crate) alts: Vec<TokenStream>,
     pub(crate) unsafe_alts: Vec<TokenStream>,
+    /// `Self::ident => <backing-typed literal>` arms for `to_bits`.
+    pub(crate) to_bits: Vec<TokenStream>,
     /// The maximum value represented by the bit encodings. This determines the number of
-    /// bits required for the encoding (`Codec::BITS`).
-    pub(crate) max_discriminant: u8,
+    /// bits required for the encoding (`Codec::BITS`). Widened to `u32` to support
+    /// `#[repr(u16)]`/`#[repr(u32)]` codecs alongside the original `u8` ones.
+    pub(crate) max_discriminant: u32,
+}
+
+/// Extract the `u64` value of an `#[alt(...)]` literal (byte or integer), so
+/// it can be validated and re-emitted as an ASCII byte literal for
+/// `try_from_ascii`.
+fn alt_lit_value(lit: &syn::ExprLit) -> Result<u64, syn::Error> {
+    match &lit.lit {
+        syn::Lit::Byte(b) => Ok(u64::from(b.value())),
+        syn::Lit::Int(i) => i.base10_parse::<u64>(),
+        _ => Err(syn::Error::new_spanned(
+            lit,
+            "Codec #[alt(...)] entries require byte or integer literals",
+        )),
+    }
 }
 
 pub(crate) fn parse_variants(
     variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    backing: BackingInt,
 ) -> Result<CodecVariants, syn::Error> {
-    let mut max_discriminant = 0u8;
+    let mut max_discriminant = 0u32;
     let mut idents = Vec::new();
 
     let mut to_chars = Vec::new();
     let mut from_chars = Vec::new();
     let mut alts = Vec::new();
     let mut unsafe_alts = Vec::new();
+    let mut to_bits = Vec::new();
 
     for variant in variants {
         let ident = &variant.ident;
@@ -105,8 +223,8 @@ pub(crate) fn parse_variants(
         if let Some((_, syn::Expr::Lit(expr_lit))) = discriminant {
             let value = match &expr_lit.lit {
                 // discriminants must be either integers or byte literals
-                syn::Lit::Byte(lit_byte) => lit_byte.value(),
-                syn::Lit::Int(lit_int) => lit_int.base10_parse::<u8>().unwrap(),
+                syn::Lit::Byte(lit_byte) => u32::from(lit_byte.value()),
+                syn::Lit::Int(lit_int) => lit_int.base10_parse::<u32>().unwrap(),
                 _ => {
                     return Err(syn::Error::new_spanned(
                         ident,
@@ -115,8 +233,14 @@ pub(crate) fn parse_variants(
                 }
             };
 
-            alts.push(quote! { #value => Some(Self::#ident) });
-            unsafe_alts.push(quote! { #value => Self::#ident });
+            // emit a literal suffixed with the enum's backing type (e.g.
+            // `5u16`) so it type-checks as a pattern against a `to_bits`/
+            // `from_bits` scrutinee of that type, instead of picking up
+            // quote's default `u32` suffix regardless of `#[repr(...)]`
+            let lit = backing.suffixed_lit(u64::from(value));
+            alts.push(quote! { #lit => Some(Self::#ident) });
+            unsafe_alts.push(quote! { #lit => Self::#ident });
+            to_bits.push(quote! { Self::#ident => #lit });
 
             max_discriminant = max_discriminant.max(value);
         } else {
@@ -138,8 +262,15 @@ pub(crate) fn parse_variants(
                 let discs: Punctuated<syn::ExprLit, Token![,]> =
                     attr.parse_args_with(Punctuated::parse_terminated)?;
                 for d in discs {
-                    alts.push(quote! { #d => Some(Self::#ident) });
-                    unsafe_alts.push(quote! { #d => Self::#ident });
+                    let value = alt_lit_value(&d)?;
+                    let byte = u8::try_from(value).map_err(|_| {
+                        syn::Error::new_spanned(
+                            &d,
+                            "Codec #[alt(...)] entries must be ASCII bytes (0..=255)",
+                        )
+                    })?;
+                    let lit = syn::LitByte::new(byte, proc_macro2::Span::call_site());
+                    from_chars.push(quote! { #lit => Some(Self::#ident) });
                 }
             }
         }
@@ -154,6 +285,96 @@ pub(crate) fn parse_variants(
         from_chars,
         alts,
         unsafe_alts,
+        to_bits,
         max_discriminant,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn variants_of(
+        item: syn::ItemEnum,
+    ) -> syn::punctuated::Punctuated<syn::Variant, syn::token::Comma> {
+        item.variants
+    }
+
+    #[test]
+    fn parse_repr_defaults_to_u8() {
+        let item: syn::ItemEnum = parse_quote! {
+            enum Dna { A = 0, C = 1, G = 2, T = 3 }
+        };
+        assert!(matches!(parse_repr(&item.attrs), Ok(BackingInt::U8)));
+    }
+
+    #[test]
+    fn parse_repr_reads_u16_u32() {
+        let u16_item: syn::ItemEnum = parse_quote! {
+            #[repr(u16)]
+            enum Wide { A = 0 }
+        };
+        assert!(matches!(parse_repr(&u16_item.attrs), Ok(BackingInt::U16)));
+
+        let u32_item: syn::ItemEnum = parse_quote! {
+            #[repr(u32)]
+            enum Wider { A = 0 }
+        };
+        assert!(matches!(parse_repr(&u32_item.attrs), Ok(BackingInt::U32)));
+    }
+
+    #[test]
+    fn parse_repr_rejects_unsupported_width() {
+        let item: syn::ItemEnum = parse_quote! {
+            #[repr(u64)]
+            enum TooWide { A = 0 }
+        };
+        assert!(parse_repr(&item.attrs).is_err());
+    }
+
+    #[test]
+    fn parse_width_widens_past_256_variants_for_u16() {
+        // a maximum discriminant of 300 needs 9 bits, which doesn't fit in a
+        // `u8`-backed codec but does in a `u16`-backed one
+        assert!(parse_width(&Vec::new(), 300, BackingInt::U8).is_err());
+        assert_eq!(parse_width(&Vec::new(), 300, BackingInt::U16).unwrap(), 9);
+    }
+
+    #[test]
+    fn parse_width_rejects_bits_exceeding_backing_capacity() {
+        let attrs: Vec<syn::Attribute> = vec![parse_quote!(#[bits(20)])];
+        assert!(parse_width(&attrs, 3, BackingInt::U16).is_err());
+        assert!(parse_width(&attrs, 3, BackingInt::U32).is_ok());
+    }
+
+    #[test]
+    fn parse_variants_suffixes_literals_with_backing_type() {
+        let item: syn::ItemEnum = parse_quote! {
+            #[repr(u16)]
+            enum Wide { A = 0, B = 300 }
+        };
+        let parsed = parse_variants(&variants_of(item), BackingInt::U16).unwrap();
+        assert_eq!(parsed.max_discriminant, 300);
+
+        let rendered: Vec<String> = parsed.to_bits.iter().map(ToString::to_string).collect();
+        assert!(rendered.iter().any(|arm| arm.contains("300u16")));
+        assert!(!rendered.iter().any(|arm| arm.contains("300u32")));
+    }
+
+    #[test]
+    fn alt_bytes_are_routed_to_from_chars_not_bits() {
+        let item: syn::ItemEnum = parse_quote! {
+            enum Gapped { A = 0, Gap = 1, #[alt(b'*')] Other = 2 }
+        };
+        let parsed = parse_variants(&variants_of(item), BackingInt::U8).unwrap();
+
+        let from_chars: Vec<String> = parsed.from_chars.iter().map(ToString::to_string).collect();
+        assert!(from_chars.iter().any(|arm| arm.contains("b'*'")));
+
+        let alts: Vec<String> = parsed.alts.iter().map(ToString::to_string).collect();
+        assert!(!alts
+            .iter()
+            .any(|arm| arm.contains("b'*'") || arm.contains("42u8")));
+    }
+}
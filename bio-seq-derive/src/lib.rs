@@ -0,0 +1,112 @@
+// Copyright 2024 Jeff Knaggs
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Derive macro for the `Codec` trait: turns a fieldless, discriminant-
+//! tagged enum into a packed bit-level alphabet.
+
+mod codec;
+
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Data, DeriveInput, parse_macro_input};
+
+use codec::{parse_repr, parse_variants, parse_width};
+
+#[proc_macro_derive(Codec, attributes(bits, display, alt))]
+pub fn derive_codec(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = &ast.ident;
+
+    let variants = match &ast.data {
+        Data::Enum(data_enum) => &data_enum.variants,
+        _ => {
+            return syn::Error::new_spanned(&ast.ident, "Codec can only be derived for enums")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    // choose the backing integer (`u8`/`u16`/`u32`) from the enum's
+    // `#[repr(...)]`, then parse the variants and requested `#[bits(N)]`
+    // width against that backing type
+    let backing = match parse_repr(&ast.attrs) {
+        Ok(backing) => backing,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let parsed = match parse_variants(variants, backing) {
+        Ok(parsed) => parsed,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let width = match parse_width(&ast.attrs, parsed.max_discriminant, backing) {
+        Ok(width) => width,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let repr_ty = backing.ty();
+    let idents = &parsed.idents;
+    let to_chars = &parsed.to_chars;
+    let from_chars = &parsed.from_chars;
+    let alts = &parsed.alts;
+    let unsafe_alts = &parsed.unsafe_alts;
+    let to_bits = &parsed.to_bits;
+
+    let expanded = quote! {
+        impl Codec for #name {
+            const BITS: u8 = #width;
+
+            type Repr = #repr_ty;
+
+            /// SAFETY: only the low `Self::BITS` bits of `b` are consulted
+            fn unsafe_from_bits(b: #repr_ty) -> Self {
+                let mask = <#repr_ty>::MAX
+                    .checked_shr(<#repr_ty>::BITS - u32::from(Self::BITS))
+                    .unwrap_or(0);
+                match b & mask {
+                    #(#unsafe_alts,)*
+                    _ => unreachable!("invalid bit pattern for this codec"),
+                }
+            }
+
+            fn try_from_bits(b: #repr_ty) -> Option<Self> {
+                match b {
+                    #(#alts,)*
+                    _ => None,
+                }
+            }
+
+            fn unsafe_from_ascii(c: u8) -> Self {
+                Self::try_from_ascii(c).unwrap()
+            }
+
+            fn try_from_ascii(c: u8) -> Option<Self> {
+                match c {
+                    #(#from_chars,)*
+                    _ => None,
+                }
+            }
+
+            fn to_char(self) -> char {
+                (match self {
+                    #(#to_chars,)*
+                }) as char
+            }
+
+            fn to_bits(self) -> #repr_ty {
+                match self {
+                    #(#to_bits,)*
+                }
+            }
+
+            fn items() -> impl Iterator<Item = Self> {
+                vec![#(Self::#idents),*].into_iter()
+            }
+        }
+    };
+
+    expanded.into()
+}
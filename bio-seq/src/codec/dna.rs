@@ -1,5 +1,8 @@
 //! 2-bit DNA representation: `A: 00, C: 01, G: 10, T: 11`
 
+use bincode::de::read::Reader;
+use bincode::enc::write::Writer;
+
 use crate::codec::Codec;
 use crate::seq::Seq;
 //use crate::kmer::Kmer;
@@ -18,6 +21,8 @@ pub enum Dna {
 impl Codec for Dna {
     const BITS: u8 = 2;
 
+    type Repr = u8;
+
     /// Transmute a `u8` into a nucleotide
     ///
     /// SAFETY: This only looks at the lower 2 bits of the `u8`
@@ -116,12 +121,103 @@ impl Seq<Dna> {
     }
 }
 
+/// Encode a bit-length as a SCALE-style compact integer so that the encoded
+/// form doesn't depend on the host's word size.
+///
+/// The two least-significant bits of the first byte select a mode:
+/// - `0b00`: single-byte mode, value `< 64` stored as `value << 2`
+/// - `0b01`: two-byte little-endian mode, value `< 2^14`
+/// - `0b10`: four-byte little-endian mode, value `< 2^30`
+/// - `0b11`: big-integer mode, the upper six bits of the first byte hold the
+///   number of following little-endian bytes minus four
+pub(crate) fn encode_compact_len<E: bincode::enc::Encoder>(
+    value: u64,
+    encoder: &mut E,
+) -> Result<(), bincode::error::EncodeError> {
+    if value < 64 {
+        encoder.writer().write(&[(value as u8) << 2])
+    } else if value < (1 << 14) {
+        let x = ((value as u16) << 2) | 0b01;
+        encoder.writer().write(&x.to_le_bytes())
+    } else if value < (1 << 30) {
+        let x = ((value as u32) << 2) | 0b10;
+        encoder.writer().write(&x.to_le_bytes())
+    } else {
+        let full = value.to_le_bytes();
+        let mut len = 8;
+        while len > 4 && full[len - 1] == 0 {
+            len -= 1;
+        }
+        encoder.writer().write(&[(((len - 4) as u8) << 2) | 0b11])?;
+        encoder.writer().write(&full[..len])
+    }
+}
+
+/// The inverse of [`encode_compact_len`].
+pub(crate) fn decode_compact_len<D: bincode::de::Decoder>(
+    decoder: &mut D,
+) -> Result<u64, bincode::error::DecodeError> {
+    let mut first = [0u8; 1];
+    decoder.reader().read(&mut first)?;
+    match first[0] & 0b11 {
+        0b00 => Ok(u64::from(first[0] >> 2)),
+        0b01 => {
+            let mut rest = [0u8; 1];
+            decoder.reader().read(&mut rest)?;
+            Ok(u64::from(u16::from_le_bytes([first[0], rest[0]]) >> 2))
+        }
+        0b10 => {
+            let mut rest = [0u8; 3];
+            decoder.reader().read(&mut rest)?;
+            let x = u32::from_le_bytes([first[0], rest[0], rest[1], rest[2]]);
+            Ok(u64::from(x >> 2))
+        }
+        _ => {
+            let len = (usize::from(first[0] >> 2)) + 4;
+            let mut rest = vec![0u8; len];
+            decoder.reader().read(&mut rest)?;
+            let mut buf = [0u8; 8];
+            buf[..len].copy_from_slice(&rest);
+            Ok(u64::from_le_bytes(buf))
+        }
+    }
+}
+
+/// Pack `usize` words into `ceil(bit_len / 8)` little-endian bytes,
+/// independent of the platform's word size.
+fn words_to_le_bytes(words: &[usize], bit_len: usize) -> Vec<u8> {
+    let byte_len = bit_len.div_ceil(8);
+    let mut bytes = Vec::with_capacity(byte_len);
+    for word in words {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+    bytes.truncate(byte_len);
+    bytes
+}
+
+/// The inverse of [`words_to_le_bytes`]: re-chunk a little-endian byte
+/// stream into the platform's native word size.
+fn le_bytes_to_words(bytes: &[u8]) -> Vec<usize> {
+    const WORD_BYTES: usize = std::mem::size_of::<usize>();
+    bytes
+        .chunks(WORD_BYTES)
+        .map(|chunk| {
+            let mut buf = [0u8; WORD_BYTES];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            usize::from_le_bytes(buf)
+        })
+        .collect()
+}
+
 impl bincode::Encode for Seq<Dna> {
     fn encode<E: bincode::enc::Encoder>(
         &self,
         encoder: &mut E,
     ) -> Result<(), bincode::error::EncodeError> {
-        bincode::Encode::encode(&(self.len(), self.into_raw()), encoder)
+        let bit_len = self.len() * usize::from(Dna::BITS);
+        encode_compact_len(bit_len as u64, encoder)?;
+        let bytes = words_to_le_bytes(&self.into_raw(), bit_len);
+        encoder.writer().write(&bytes)
     }
 }
 
@@ -129,8 +225,11 @@ impl<Context> bincode::Decode<Context> for Seq<Dna> {
     fn decode<D: bincode::de::Decoder<Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, bincode::error::DecodeError> {
-        let (len, bits): (usize, Vec<usize>) = bincode::Decode::decode(decoder)?;
-        Self::from_raw(len, &bits).ok_or(bincode::error::DecodeError::Other(
+        let bit_len = decode_compact_len(decoder)? as usize;
+        let len = bit_len / usize::from(Dna::BITS);
+        let mut bytes = vec![0u8; bit_len.div_ceil(8)];
+        decoder.reader().read(&mut bytes)?;
+        Self::from_raw(len, &le_bytes_to_words(&bytes)).ok_or(bincode::error::DecodeError::Other(
             "Failed to recreate the DNA sequence from its raw parts",
         ))
     }
@@ -140,8 +239,11 @@ impl<'de, Context> bincode::BorrowDecode<'de, Context> for Seq<Dna> {
     fn borrow_decode<D: bincode::de::BorrowDecoder<'de, Context = Context>>(
         decoder: &mut D,
     ) -> Result<Self, bincode::error::DecodeError> {
-        let (len, bits): (usize, Vec<usize>) = bincode::BorrowDecode::borrow_decode(decoder)?;
-        Self::from_raw(len, &bits).ok_or(bincode::error::DecodeError::Other(
+        let bit_len = decode_compact_len(decoder)? as usize;
+        let len = bit_len / usize::from(Dna::BITS);
+        let mut bytes = vec![0u8; bit_len.div_ceil(8)];
+        decoder.reader().read(&mut bytes)?;
+        Self::from_raw(len, &le_bytes_to_words(&bytes)).ok_or(bincode::error::DecodeError::Other(
             "Failed to recreate the DNA sequence from its raw parts",
         ))
     }
@@ -196,6 +298,37 @@ mod tests {
         assert_eq!(dna, dna_roundtrip);
     }
 
+    #[test]
+    fn bincode_compact_len_roundtrip() {
+        // exercise the single-byte, two-byte, and four-byte compact-length
+        // modes by growing the sequence past each mode's boundary; the
+        // big-integer mode is not reached
+        let config = bincode::config::standard();
+        for bases in [0, 1, 16, 40, 5_000, 300_000] {
+            let dna: Seq<Dna> = (0..bases)
+                .map(|i| Dna::unsafe_from_bits((i % 4) as u8))
+                .collect();
+
+            let encoded = bincode::encode_to_vec(&dna, config).unwrap();
+            let decoded: Seq<Dna> = bincode::decode_from_slice(&encoded, config).unwrap().0;
+            assert_eq!(dna, decoded);
+        }
+    }
+
+    #[test]
+    fn bincode_compact_len_is_byte_oriented() {
+        // a short sequence should serialize to a single-byte length prefix
+        // plus the packed bytes, not a platform-sized `usize` length field
+        let dna = dna!("ACGT").to_owned();
+        let config = bincode::config::standard();
+        let encoded = bincode::encode_to_vec(&dna, config).unwrap();
+
+        // 4 bases * 2 bits = 8 bits -> single-byte compact length (0b00 mode)
+        // followed by ceil(8 / 8) = 1 packed byte
+        assert_eq!(encoded.len(), 2);
+        assert_eq!(encoded[0], 8 << 2);
+    }
+
     /*
     #[test]
     fn dna_kmer_complement() {
@@ -0,0 +1,41 @@
+//! `Codec`: a fixed-width bit-level alphabet that `Seq`/`Kmer` pack symbols
+//! from, plus the built-in codecs that implement it.
+
+pub mod dna;
+
+/// A symbol alphabet with a fixed bit-packed representation.
+///
+/// `Repr` is the unsigned integer type that holds one packed symbol. `Dna`
+/// uses `u8`; the `#[derive(Codec)]` macro takes `u8`, `u16` or `u32` from
+/// the enum's `#[repr(...)]` attribute (`u8` when there is none), so a
+/// derived `Repr` can be wider than `BITS` needs.
+pub trait Codec: Sized + Copy {
+    /// Number of bits used to pack one symbol.
+    const BITS: u8;
+
+    /// The unsigned integer type that stores one packed symbol.
+    type Repr: Copy;
+
+    /// Transmute a `Repr` into a symbol without validating its value.
+    ///
+    /// SAFETY: only the low `Self::BITS` bits of `b` are consulted.
+    fn unsafe_from_bits(b: Self::Repr) -> Self;
+
+    /// Validate and convert a `Repr` into a symbol.
+    fn try_from_bits(b: Self::Repr) -> Option<Self>;
+
+    /// Transmute an ASCII byte into a symbol without validating its value.
+    fn unsafe_from_ascii(b: u8) -> Self;
+
+    /// Validate and convert an ASCII byte into a symbol.
+    fn try_from_ascii(c: u8) -> Option<Self>;
+
+    /// The ASCII character a symbol is printed as.
+    fn to_char(self) -> char;
+
+    /// The packed bit representation of a symbol.
+    fn to_bits(self) -> Self::Repr;
+
+    /// Every symbol in the alphabet, in the order the variants are declared.
+    fn items() -> impl Iterator<Item = Self>;
+}
@@ -0,0 +1,370 @@
+//! Entropy-compressed sequence representation using canonical Huffman coding.
+//!
+//! Fixed-width bit packing (e.g. 2 bits/base for `Dna`) is wasteful for
+//! sequences with skewed symbol composition, such as AT-rich genomes or long
+//! homopolymer runs. [`HuffSeq`] instead assigns each symbol of a `Codec` a
+//! variable-length code derived from its frequency within a particular
+//! sequence, built as a *canonical* Huffman code so that the decode table is
+//! just one small per-symbol code-length array rather than the codes
+//! themselves.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::marker::PhantomData;
+
+use bincode::de::read::Reader;
+use bincode::enc::write::Writer;
+
+use crate::codec::dna::{decode_compact_len, encode_compact_len};
+use crate::codec::Codec;
+use crate::seq::Seq;
+
+/// A sequence of `C` symbols stored as a canonical Huffman code instead of
+/// `Seq<C>`'s fixed-width bit packing.
+///
+/// The payload conceptually is `[code length table][bit-packed codes][symbol
+/// count]`: the length table holds one length per symbol of `C::items()` (0
+/// meaning the symbol never occurs), which is enough to rebuild the
+/// canonical code without storing the codes themselves.
+pub struct HuffSeq<C: Codec> {
+    /// Code length, in bits, assigned to each symbol in `C::items()` order.
+    lengths: Vec<u8>,
+    /// The Huffman-coded bits, packed MSB-first.
+    packed: Vec<u8>,
+    /// Number of symbols encoded; needed because `packed` may be padded out
+    /// to a whole number of bytes.
+    count: usize,
+    _codec: PhantomData<C>,
+}
+
+/// A node in the (non-canonical) Huffman tree built from symbol frequencies.
+/// Only used transiently to compute code lengths.
+enum Node {
+    Leaf { symbol: usize },
+    Internal { left: Box<Node>, right: Box<Node> },
+}
+
+/// Wraps a `Node` with the weight it was merged under, giving us an `Ord`
+/// impl so the pair can live in a `BinaryHeap` as a min-heap.
+struct HeapEntry {
+    freq: usize,
+    order: usize,
+    node: Node,
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.freq == other.freq && self.order == other.order
+    }
+}
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed so that `BinaryHeap` (a max-heap) pops the lowest
+        // frequency first; ties broken by insertion order for determinism
+        other
+            .freq
+            .cmp(&self.freq)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compute a Huffman code length per symbol (indexed as in `C::items()`)
+/// from symbol frequencies, handling the single-symbol-alphabet edge case by
+/// assigning a length-1 code.
+fn build_lengths(freqs: &[usize]) -> Vec<u8> {
+    let mut lengths = vec![0u8; freqs.len()];
+    let present: Vec<usize> = (0..freqs.len()).filter(|&i| freqs[i] > 0).collect();
+
+    if present.len() <= 1 {
+        for i in present {
+            lengths[i] = 1;
+        }
+        return lengths;
+    }
+
+    let mut heap: BinaryHeap<HeapEntry> = BinaryHeap::new();
+    for (order, &i) in present.iter().enumerate() {
+        heap.push(HeapEntry {
+            freq: freqs[i],
+            order,
+            node: Node::Leaf { symbol: i },
+        });
+    }
+
+    let mut next_order = present.len();
+    while heap.len() > 1 {
+        let a = heap.pop().unwrap();
+        let b = heap.pop().unwrap();
+        heap.push(HeapEntry {
+            freq: a.freq + b.freq,
+            order: next_order,
+            node: Node::Internal {
+                left: Box::new(a.node),
+                right: Box::new(b.node),
+            },
+        });
+        next_order += 1;
+    }
+
+    fn assign_depths(node: &Node, depth: u8, lengths: &mut [u8]) {
+        match node {
+            Node::Leaf { symbol } => lengths[*symbol] = depth,
+            Node::Internal { left, right } => {
+                assign_depths(left, depth + 1, lengths);
+                assign_depths(right, depth + 1, lengths);
+            }
+        }
+    }
+    assign_depths(&heap.pop().unwrap().node, 0, &mut lengths);
+    lengths
+}
+
+/// Derive canonical codes from a per-symbol length table: sort symbols by
+/// `(length, position in C::items())`, give the first code `0`, and for each
+/// subsequent symbol increment the previous code and left-shift it by the
+/// increase in length.
+fn canonical_codes(lengths: &[u8]) -> Vec<(usize, u32, u8)> {
+    let mut symbols: Vec<usize> = (0..lengths.len()).filter(|&i| lengths[i] > 0).collect();
+    symbols.sort_by_key(|&i| (lengths[i], i));
+
+    let mut codes = Vec::with_capacity(symbols.len());
+    let mut code: u32 = 0;
+    let mut prev_len = symbols.first().map_or(0, |&i| lengths[i]);
+    for i in symbols {
+        let len = lengths[i];
+        code <<= len - prev_len;
+        codes.push((i, code, len));
+        code += 1;
+        prev_len = len;
+    }
+    codes
+}
+
+/// MSB-first bit packer.
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit_pos: u8,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter {
+            bytes: Vec::new(),
+            bit_pos: 0,
+        }
+    }
+
+    fn push_bits(&mut self, code: u32, len: u8) {
+        for i in (0..len).rev() {
+            if self.bit_pos == 0 {
+                self.bytes.push(0);
+            }
+            if (code >> i) & 1 == 1 {
+                *self.bytes.last_mut().unwrap() |= 1 << (7 - self.bit_pos);
+            }
+            self.bit_pos = (self.bit_pos + 1) % 8;
+        }
+    }
+}
+
+/// MSB-first bit reader over a byte slice.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit_pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        BitReader { bytes, bit_pos: 0 }
+    }
+
+    fn next_bit(&mut self) -> u32 {
+        let byte = self.bytes[self.bit_pos / 8];
+        let bit = (byte >> (7 - (self.bit_pos % 8))) & 1;
+        self.bit_pos += 1;
+        u32::from(bit)
+    }
+}
+
+impl<C: Codec> HuffSeq<C>
+where
+    C::Repr: Into<u64>,
+{
+    /// Build a canonical-Huffman-compressed sequence from `seq`'s symbol
+    /// frequencies.
+    pub fn from_seq(seq: &Seq<C>) -> Self {
+        let symbols: Vec<C> = C::items().collect();
+        // `to_bits()` is not guaranteed to be a dense index into `symbols`
+        // (discriminants can be sparse, e.g. codon or IUPAC-style tables),
+        // so map each symbol's bit value to its position in `symbols`
+        // rather than indexing by the bit value directly.
+        let index_of: std::collections::HashMap<u64, usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.to_bits().into(), i))
+            .collect();
+
+        let mut freqs = vec![0usize; symbols.len()];
+        for base in seq {
+            freqs[index_of[&base.to_bits().into()]] += 1;
+        }
+
+        let lengths = build_lengths(&freqs);
+        let mut code_by_symbol = vec![(0u32, 0u8); symbols.len()];
+        for (i, code, len) in canonical_codes(&lengths) {
+            code_by_symbol[i] = (code, len);
+        }
+
+        let mut writer = BitWriter::new();
+        let mut count = 0usize;
+        for base in seq {
+            let (code, len) = code_by_symbol[index_of[&base.to_bits().into()]];
+            writer.push_bits(code, len);
+            count += 1;
+        }
+
+        HuffSeq {
+            lengths,
+            packed: writer.bytes,
+            count,
+            _codec: PhantomData,
+        }
+    }
+
+    /// Rebuild the original sequence by walking the bitstream and matching
+    /// each prefix against the canonical first-code/first-index table for
+    /// its length.
+    pub fn to_seq(&self) -> Seq<C> {
+        let symbols: Vec<C> = C::items().collect();
+        let codes = canonical_codes(&self.lengths);
+
+        // first_code[len] / first_index[len]: the canonical code assigned
+        // to the first symbol of each length, and that symbol's position in
+        // `codes`, so a candidate code can be resolved in O(1) per bit.
+        let max_len = codes.iter().map(|&(_, _, len)| len).max().unwrap_or(0) as usize;
+        let mut first_code = vec![0u32; max_len + 1];
+        let mut first_index = vec![0usize; max_len + 1];
+        let mut counts = vec![0u32; max_len + 1];
+        for (idx, &(_, code, len)) in codes.iter().enumerate() {
+            let len = len as usize;
+            if counts[len] == 0 {
+                first_code[len] = code;
+                first_index[len] = idx;
+            }
+            counts[len] += 1;
+        }
+
+        let mut reader = BitReader::new(&self.packed);
+        let mut out = Vec::with_capacity(self.count);
+        for _ in 0..self.count {
+            let mut code = 0u32;
+            let mut len = 0usize;
+            loop {
+                code = (code << 1) | reader.next_bit();
+                len += 1;
+                if len < counts.len()
+                    && counts[len] > 0
+                    && code >= first_code[len]
+                    && code - first_code[len] < counts[len]
+                {
+                    let symbol_idx = codes[first_index[len] + (code - first_code[len]) as usize].0;
+                    out.push(symbols[symbol_idx]);
+                    break;
+                }
+            }
+        }
+        out.into_iter().collect()
+    }
+}
+
+impl bincode::Encode for HuffSeq<crate::codec::dna::Dna> {
+    fn encode<E: bincode::enc::Encoder>(
+        &self,
+        encoder: &mut E,
+    ) -> Result<(), bincode::error::EncodeError> {
+        encoder.writer().write(&self.lengths)?;
+        encode_compact_len(self.packed.len() as u64, encoder)?;
+        encoder.writer().write(&self.packed)?;
+        encode_compact_len(self.count as u64, encoder)
+    }
+}
+
+impl<Context> bincode::Decode<Context> for HuffSeq<crate::codec::dna::Dna> {
+    fn decode<D: bincode::de::Decoder<Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, bincode::error::DecodeError> {
+        let mut lengths = vec![0u8; crate::codec::dna::Dna::items().count()];
+        decoder.reader().read(&mut lengths)?;
+        let packed_len = decode_compact_len(decoder)? as usize;
+        let mut packed = vec![0u8; packed_len];
+        decoder.reader().read(&mut packed)?;
+        let count = decode_compact_len(decoder)? as usize;
+
+        Ok(HuffSeq {
+            lengths,
+            packed,
+            count,
+            _codec: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::HuffSeq;
+
+    #[test]
+    fn empty_sequence_roundtrip() {
+        let empty: Seq<Dna> = std::iter::empty().collect();
+        let huff = HuffSeq::from_seq(&empty);
+        assert_eq!(huff.to_seq(), empty);
+    }
+
+    #[test]
+    fn single_symbol_alphabet_roundtrip() {
+        // every base is the same symbol, so the huffman tree degenerates to
+        // a single length-1 code rather than a zero-length one
+        let dna = dna!("AAAAAAAAAA").to_owned();
+        let huff = HuffSeq::from_seq(&dna);
+        assert_eq!(huff.to_seq(), dna);
+    }
+
+    #[test]
+    fn skewed_composition_roundtrip() {
+        // heavily AT-biased, so A/T should get shorter codes than C/G
+        let dna = dna!("AAAAAAAAAATTTTTTTTTTAACCGG").to_owned();
+        let huff = HuffSeq::from_seq(&dna);
+        assert_eq!(huff.to_seq(), dna);
+    }
+
+    #[test]
+    fn uniform_composition_equal_length_roundtrip() {
+        // every base occurs equally often, so all four get length-2 codes
+        let dna = dna!("ACGTACGTACGTACGTACGT").to_owned();
+        let huff = HuffSeq::from_seq(&dna);
+        assert_eq!(huff.to_seq(), dna);
+    }
+
+    #[test]
+    fn bincode_roundtrip() {
+        let dna = dna!("AAAAAAAAAATTTTTTCCGG").to_owned();
+        let huff = HuffSeq::from_seq(&dna);
+
+        let config = bincode::config::standard();
+        let decoded: HuffSeq<Dna> =
+            bincode::decode_from_slice(&bincode::encode_to_vec(&huff, config).unwrap(), config)
+                .unwrap()
+                .0;
+
+        assert_eq!(decoded.to_seq(), dna);
+    }
+}
@@ -0,0 +1,333 @@
+//! Streaming decoder for building sequences from byte/IO sources without
+//! first buffering the whole input in memory.
+//!
+//! All of the other construction paths (`try_from_ascii`, the `dna!` macro,
+//! bincode decode) assume the input already lives in memory as a single
+//! buffer. [`CodecReader`] instead pulls bytes from an `io::Read` on demand,
+//! so a multi-gigabyte FASTA/FASTQ file or a network stream never needs to
+//! be materialised all at once.
+
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+
+use crate::codec::Codec;
+use crate::seq::Seq;
+
+/// How [`CodecReader`] should handle a byte that `C::try_from_ascii` rejects
+/// (and that isn't a line break, FASTA header, or FASTQ record line).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidPolicy {
+    /// Stop iteration and return a [`DecodeError`].
+    Error,
+    /// Silently drop the byte and keep reading.
+    Skip,
+    /// Retry the byte's uppercase form before giving up. This is plain
+    /// case-folding, not a separate lookup through the codec's `#[alt]`
+    /// table: `#[alt(...)]` bytes are `Codec::try_from_ascii` arms in their
+    /// own right, so the initial `C::try_from_ascii` call already resolves
+    /// them, and there is no distinct "alt" fallback left for a policy to
+    /// perform -- a byte reaches `decode_byte`'s fallback only once both the
+    /// primary and `#[alt]` arms have already rejected it.
+    CaseFold,
+}
+
+/// An error produced while decoding a streamed sequence.
+#[derive(Debug)]
+pub enum DecodeError {
+    Io(io::Error),
+    /// A byte that `C::try_from_ascii` could not resolve under the active
+    /// [`InvalidPolicy`].
+    InvalidSymbol(u8),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Io(e) => write!(f, "I/O error while decoding sequence: {e}"),
+            DecodeError::InvalidSymbol(b) => write!(f, "invalid symbol byte: {b:#04x}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<io::Error> for DecodeError {
+    fn from(e: io::Error) -> Self {
+        DecodeError::Io(e)
+    }
+}
+
+/// Incrementally decodes `Seq<C>` chunks from an underlying `io::Read`.
+///
+/// Recognises both FASTA and FASTQ record framing line-by-line:
+/// - a line starting with `>` or `;` is a FASTA header/comment and is
+///   skipped whole
+/// - a line starting with `@` is a FASTQ read id and is skipped whole
+/// - a line starting with `+` is a FASTQ separator; it and the *entire*
+///   quality line that follows are skipped whole, regardless of what bytes
+///   the quality scores happen to contain (a Phred score can coincide with
+///   an ASCII base letter, so quality lines are never run through
+///   `C::try_from_ascii`)
+///
+/// Every other byte is a sequence byte and is passed to `C::try_from_ascii`.
+pub struct CodecReader<C: Codec, R: io::Read> {
+    inner: R,
+    chunk_size: usize,
+    policy: InvalidPolicy,
+    /// `true` at the first byte of a line, where the line's role (header,
+    /// separator, or sequence/quality data) is decided.
+    at_line_start: bool,
+    /// `true` while consuming the remainder of a line whose bytes are not
+    /// sequence data and must not reach `C::try_from_ascii`.
+    skip_rest_of_line: bool,
+    /// Set by a `+` separator line so the *next* line is treated as FASTQ
+    /// quality data and skipped whole, without sniffing its first byte.
+    next_line_is_quality: bool,
+    done: bool,
+    _codec: PhantomData<C>,
+}
+
+impl<C: Codec, R: io::Read> CodecReader<C, R> {
+    /// Create a reader that yields 4096-symbol chunks and errors on invalid
+    /// bytes.
+    pub fn new(inner: R) -> Self {
+        Self::with_policy(inner, 4096, InvalidPolicy::Error)
+    }
+
+    /// Create a reader with a configurable chunk size and invalid-byte
+    /// policy.
+    pub fn with_policy(inner: R, chunk_size: usize, policy: InvalidPolicy) -> Self {
+        CodecReader {
+            inner,
+            chunk_size,
+            policy,
+            at_line_start: true,
+            skip_rest_of_line: false,
+            next_line_is_quality: false,
+            done: false,
+            _codec: PhantomData,
+        }
+    }
+
+    /// Pull the next raw symbol byte from the stream, skipping line breaks,
+    /// FASTA headers, and FASTQ id/separator/quality lines. Returns
+    /// `Ok(None)` at end of input.
+    fn next_symbol_byte(&mut self) -> Result<Option<u8>, DecodeError> {
+        let mut byte = [0u8; 1];
+        loop {
+            if self.inner.read(&mut byte)? == 0 {
+                return Ok(None);
+            }
+            let b = byte[0];
+
+            if b == b'\n' {
+                self.at_line_start = true;
+                self.skip_rest_of_line = false;
+                continue;
+            }
+            if b == b'\r' {
+                continue;
+            }
+
+            if self.at_line_start {
+                self.at_line_start = false;
+                if self.next_line_is_quality {
+                    self.next_line_is_quality = false;
+                    self.skip_rest_of_line = true;
+                } else {
+                    match b {
+                        b'>' | b';' | b'@' => self.skip_rest_of_line = true,
+                        b'+' => {
+                            self.skip_rest_of_line = true;
+                            self.next_line_is_quality = true;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            if !self.skip_rest_of_line {
+                return Ok(Some(b));
+            }
+        }
+    }
+
+    /// Resolve a raw byte into a symbol under the active [`InvalidPolicy`].
+    /// `Ok(None)` means the byte was dropped and reading should continue.
+    fn decode_byte(&self, b: u8) -> Result<Option<C>, DecodeError> {
+        if let Some(sym) = C::try_from_ascii(b) {
+            return Ok(Some(sym));
+        }
+        match self.policy {
+            InvalidPolicy::Error => Err(DecodeError::InvalidSymbol(b)),
+            InvalidPolicy::Skip => Ok(None),
+            InvalidPolicy::CaseFold => C::try_from_ascii(b.to_ascii_uppercase())
+                .map(Some)
+                .ok_or(DecodeError::InvalidSymbol(b)),
+        }
+    }
+
+    /// Read up to `n` bases into a freshly built sequence, stopping early if
+    /// the stream runs out first.
+    ///
+    /// Named and shaped differently from `std::io::Read::read_exact`
+    /// on purpose: `Seq` doesn't expose in-place growth, so there is no
+    /// existing sequence to fill, and this builds a new one instead. A short
+    /// underlying stream is therefore not an error here either -- check the
+    /// returned sequence's length against `n` if that distinction matters.
+    pub fn read_up_to(&mut self, n: usize) -> Result<Seq<C>, DecodeError> {
+        let mut symbols = Vec::with_capacity(n);
+        while symbols.len() < n {
+            match self.next_symbol_byte()? {
+                None => break,
+                Some(b) => {
+                    if let Some(sym) = self.decode_byte(b)? {
+                        symbols.push(sym);
+                    }
+                }
+            }
+        }
+        Ok(symbols.into_iter().collect())
+    }
+}
+
+impl<C: Codec, R: io::Read> Iterator for CodecReader<C, R> {
+    type Item = Result<Seq<C>, DecodeError>;
+
+    /// On [`InvalidPolicy::Error`], a rejected byte ends the iterator for
+    /// good (`self.done` is set before returning): the next call returns
+    /// `None` rather than resuming mid-stream, and any bases already
+    /// decoded into the current, not-yet-yielded chunk are discarded along
+    /// with it.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut symbols = Vec::with_capacity(self.chunk_size);
+        while symbols.len() < self.chunk_size {
+            match self.next_symbol_byte() {
+                Ok(None) => {
+                    self.done = true;
+                    break;
+                }
+                Ok(Some(b)) => match self.decode_byte(b) {
+                    Ok(Some(sym)) => symbols.push(sym),
+                    Ok(None) => continue,
+                    Err(e) => {
+                        self.done = true;
+                        return Some(Err(e));
+                    }
+                },
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        if symbols.is_empty() {
+            None
+        } else {
+            Some(Ok(symbols.into_iter().collect()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::prelude::*;
+
+    use super::{CodecReader, InvalidPolicy};
+
+    fn collect_all(reader: CodecReader<Dna, Cursor<&[u8]>>) -> Seq<Dna> {
+        reader
+            .collect::<Result<Vec<Seq<Dna>>, _>>()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn plain_sequence_with_line_breaks() {
+        let input = b"ACGT\nACGT\n";
+        let reader = CodecReader::<Dna, _>::new(Cursor::new(&input[..]));
+        assert_eq!(collect_all(reader), dna!("ACGTACGT").to_owned());
+    }
+
+    #[test]
+    fn fasta_headers_are_skipped() {
+        let input = b">seq1 description\nACGT\n;comment\nACGT\n";
+        let reader = CodecReader::<Dna, _>::new(Cursor::new(&input[..]));
+        assert_eq!(collect_all(reader), dna!("ACGTACGT").to_owned());
+    }
+
+    #[test]
+    fn fastq_quality_line_starting_with_a_base_letter_is_skipped_whole() {
+        // the quality line below starts with `A` and `@`, which would
+        // decode as bases if it weren't recognised as a quality line
+        let input = b"@read1\nACGT\n+\nA@@@\n@read2\nTTTT\n+read2\n@IAI\n";
+        let reader = CodecReader::<Dna, _>::new(Cursor::new(&input[..]));
+        assert_eq!(collect_all(reader), dna!("ACGTTTTT").to_owned());
+    }
+
+    #[test]
+    fn chunks_never_exceed_the_configured_size() {
+        let input = b"ACGTACGTACGT";
+        let reader =
+            CodecReader::<Dna, _>::with_policy(Cursor::new(&input[..]), 5, InvalidPolicy::Error);
+        let chunks: Vec<Seq<Dna>> = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| c.len() <= 5));
+        assert_eq!(
+            chunks.into_iter().flatten().collect::<Seq<Dna>>(),
+            dna!("ACGTACGTACGT").to_owned()
+        );
+    }
+
+    #[test]
+    fn error_policy_stops_on_invalid_byte() {
+        let input = b"ACNT";
+        let mut reader =
+            CodecReader::<Dna, _>::with_policy(Cursor::new(&input[..]), 4096, InvalidPolicy::Error);
+        match reader.next() {
+            Some(Err(super::DecodeError::InvalidSymbol(b))) => assert_eq!(b, b'N'),
+            other => panic!("expected InvalidSymbol(b'N'), got {other:?}"),
+        }
+        // the iterator is done for good after an error, not just paused
+        // mid-stream
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn skip_policy_drops_invalid_bytes() {
+        let input = b"ACNT";
+        let reader =
+            CodecReader::<Dna, _>::with_policy(Cursor::new(&input[..]), 4096, InvalidPolicy::Skip);
+        assert_eq!(collect_all(reader), dna!("ACT").to_owned());
+    }
+
+    #[test]
+    fn case_fold_policy_uppercases_before_giving_up() {
+        let input = b"acgt";
+        let reader = CodecReader::<Dna, _>::with_policy(
+            Cursor::new(&input[..]),
+            4096,
+            InvalidPolicy::CaseFold,
+        );
+        assert_eq!(collect_all(reader), dna!("ACGT").to_owned());
+    }
+
+    #[test]
+    fn read_up_to_stops_early_on_short_stream() {
+        let input = b"ACGT";
+        let mut reader = CodecReader::<Dna, _>::new(Cursor::new(&input[..]));
+        let seq = reader.read_up_to(10).unwrap();
+        assert_eq!(seq, dna!("ACGT").to_owned());
+    }
+}
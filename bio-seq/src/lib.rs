@@ -0,0 +1,5 @@
+//! bio-seq: bit-packed representations of biological sequences.
+
+pub mod codec;
+pub mod huffman;
+pub mod io;